@@ -1,6 +1,15 @@
 use anyhow::{anyhow, Result};
 
-use crate::{plan::DeletePlan, table::TableHeap, tuple::Tuple};
+use crate::{
+    common::INVALID_PAGE_ID,
+    page::{
+        overflow_page::OverflowPage,
+        table_page::{TablePage, TupleSlot},
+    },
+    plan::DeletePlan,
+    table::TableHeap,
+    tuple::{Tuple, RID},
+};
 
 use super::{Executor, ExecutorContext};
 
@@ -32,10 +41,38 @@ impl DeleteExecutor<'_> {
                 anyhow!("table_heap is not initialized. call init() before calling next()")
             })?;
             let rid = row.rid.ok_or_else(|| anyhow!("rid is None"))?;
-            table_heap.delete(rid)?;
+            // An overflow tuple's chain lives outside `table_heap`'s own page, so it has to be
+            // freed through the buffer pool before the line pointer itself is reclaimed; a plain
+            // inline tuple has no chain and goes through `table_heap.delete` as before.
+            if !self.free_overflow_chain(&rid)? {
+                table_heap.delete(rid)?;
+            }
             self.count += 1;
             return Ok(Some(Tuple::new(None, &vec![])));
         }
         Ok(None)
     }
+    /// If `rid` points at an out-of-line tuple, walks and frees its overflow chain and
+    /// tombstones the line pointer, returning `true`. Returns `false` for an inline tuple,
+    /// leaving it for `table_heap.delete` to tombstone in the usual way.
+    fn free_overflow_chain(&self, rid: &RID) -> Result<bool> {
+        let buffer_pool_manager = &self.executor_context.buffer_pool_manager;
+        let page_data = buffer_pool_manager.fetch_page(rid.page_id);
+        let mut page = TablePage::from_data(&page_data)?;
+        let first_page_id = match page.get_tuple_slot(rid.slot as usize) {
+            TupleSlot::Inline(_) => return Ok(false),
+            TupleSlot::Overflow { first_page_id, .. } => first_page_id,
+        };
+        let mut overflow_page_id = first_page_id;
+        while overflow_page_id != INVALID_PAGE_ID {
+            let overflow_data = buffer_pool_manager.fetch_page(overflow_page_id);
+            let overflow_page = OverflowPage::from_data(&overflow_data);
+            let next_page_id = overflow_page.next_page_id();
+            buffer_pool_manager.free_page(overflow_page_id);
+            overflow_page_id = next_page_id;
+        }
+        page.free_overflow_slot(rid.slot as u32);
+        buffer_pool_manager.write_page(rid.page_id, &page.to_bytes());
+        Ok(true)
+    }
 }
\ No newline at end of file
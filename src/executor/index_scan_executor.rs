@@ -0,0 +1,97 @@
+use anyhow::{anyhow, Result};
+
+use crate::{
+    index::{Index, PageStore},
+    page::{btree_branch_page::BTreeBranchPage, btree_leaf_page::BTreeLeafPage},
+    plan::IndexScanPlan,
+    table::TableHeap,
+    tuple::{Tuple, RID},
+};
+
+use super::{Executor, ExecutorContext};
+
+/// Implements `index::PageStore` over `ExecutorContext`'s buffer pool, so `IndexScanExecutor`
+/// can hand `Index` a way to fetch/write/allocate B-tree pages without `Index` itself knowing
+/// about the buffer pool.
+struct BufferPoolPageStore<'a> {
+    executor_context: &'a ExecutorContext,
+}
+impl PageStore for BufferPoolPageStore<'_> {
+    fn fetch_leaf(&mut self, page_id: crate::common::PageID) -> BTreeLeafPage {
+        let page = self.executor_context.buffer_pool_manager.fetch_page(page_id);
+        BTreeLeafPage::from_data(&page)
+    }
+    fn fetch_branch(&mut self, page_id: crate::common::PageID) -> BTreeBranchPage {
+        let page = self.executor_context.buffer_pool_manager.fetch_page(page_id);
+        BTreeBranchPage::from_data(&page)
+    }
+    fn write_leaf(&mut self, page: &BTreeLeafPage) {
+        self.executor_context
+            .buffer_pool_manager
+            .write_page(page.page_id(), &page.data);
+    }
+    fn write_branch(&mut self, page: &BTreeBranchPage) {
+        self.executor_context
+            .buffer_pool_manager
+            .write_page(page.page_id(), &page.data);
+    }
+    fn new_leaf(&mut self) -> BTreeLeafPage {
+        let page_id = self.executor_context.buffer_pool_manager.new_page();
+        BTreeLeafPage::new(page_id)
+    }
+    fn new_branch(&mut self, first_child_page_id: crate::common::PageID) -> BTreeBranchPage {
+        let page_id = self.executor_context.buffer_pool_manager.new_page();
+        BTreeBranchPage::new(page_id, first_child_page_id)
+    }
+}
+
+pub struct IndexScanExecutor<'a> {
+    pub plan: IndexScanPlan,
+    pub executor_context: &'a ExecutorContext,
+    pub table_heap: Option<TableHeap>,
+    rids: Vec<RID>,
+    cursor: usize,
+}
+
+impl<'a> IndexScanExecutor<'a> {
+    pub fn new(plan: IndexScanPlan, executor_context: &'a ExecutorContext) -> Self {
+        IndexScanExecutor {
+            plan,
+            executor_context,
+            table_heap: None,
+            rids: Vec::new(),
+            cursor: 0,
+        }
+    }
+    pub fn init(&mut self) -> Result<()> {
+        let txn_id = self.executor_context.transaction_id;
+        self.table_heap = Some(TableHeap::new(
+            self.plan.first_page_id,
+            self.executor_context.buffer_pool_manager.clone(),
+            self.executor_context.transaction_manager.clone(),
+            self.executor_context.lock_manager.clone(),
+            txn_id,
+        ));
+        let index = Index::new(self.plan.index_first_page_id);
+        let mut store = BufferPoolPageStore {
+            executor_context: self.executor_context,
+        };
+        self.rids = index.scan(&self.plan.range, &mut store);
+        self.cursor = 0;
+        Ok(())
+    }
+    pub fn next(&mut self) -> Result<Option<Tuple>> {
+        let table_heap = self.table_heap.as_mut().ok_or_else(|| {
+            anyhow!("table_heap is not initialized. call init() before calling next()")
+        })?;
+        while self.cursor < self.rids.len() {
+            let rid = self.rids[self.cursor].clone();
+            self.cursor += 1;
+            if let Some(tuple) = table_heap.get_tuple(rid)? {
+                return Ok(Some(tuple));
+            }
+        }
+        Ok(None)
+    }
+}
+
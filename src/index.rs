@@ -0,0 +1,201 @@
+use anyhow::Result;
+
+use crate::{
+    common::{PageID, INVALID_PAGE_ID},
+    page::{
+        btree_branch_page::BTreeBranchPage, btree_leaf_page::BTreeLeafPage,
+        btree_leaf_page::BTREE_LEAF_PAGE_PAGE_TYPE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE,
+    },
+    tuple::RID,
+};
+
+fn page_type_of(data: &[u8]) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes[..PAGE_TYPE_SIZE].copy_from_slice(&data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]);
+    u32::from_le_bytes(bytes)
+}
+
+/// An equality or bounded range over an index's (`i64`-encoded) key, as produced by the
+/// planner from a `Filter` condition over an indexed column.
+#[derive(Debug, Clone)]
+pub enum IndexRange {
+    Equals(i64),
+    Range { start: Option<i64>, end: Option<i64> },
+}
+
+impl IndexRange {
+    fn contains(&self, key: i64) -> bool {
+        match self {
+            IndexRange::Equals(target) => key == *target,
+            IndexRange::Range { start, end } => {
+                start.map_or(true, |s| key >= s) && end.map_or(true, |e| key < e)
+            }
+        }
+    }
+    fn lower_bound(&self) -> Option<i64> {
+        match self {
+            IndexRange::Equals(target) => Some(*target),
+            IndexRange::Range { start, .. } => *start,
+        }
+    }
+    /// Whether `key` is already past the range, i.e. no later (sorted-ascending) key could
+    /// still match, so a scan can stop.
+    fn is_past(&self, key: i64) -> bool {
+        match self {
+            IndexRange::Equals(target) => key > *target,
+            IndexRange::Range { end, .. } => end.is_some_and(|e| key >= e),
+        }
+    }
+}
+
+/// The page I/O an `Index` needs from the buffer pool: fetch a page by id, persist a page back,
+/// or allocate a fresh one. `Index` is generic over this rather than calling the buffer pool
+/// directly, so it stays pure B-tree page-layout logic with no buffer pool dependency of its
+/// own.
+pub trait PageStore {
+    fn fetch_leaf(&mut self, page_id: PageID) -> BTreeLeafPage;
+    fn fetch_branch(&mut self, page_id: PageID) -> BTreeBranchPage;
+    fn write_leaf(&mut self, page: &BTreeLeafPage);
+    fn write_branch(&mut self, page: &BTreeBranchPage);
+    fn new_leaf(&mut self) -> BTreeLeafPage;
+    fn new_branch(&mut self, first_child_page_id: PageID) -> BTreeBranchPage;
+}
+
+/// A disk-backed B-tree secondary index over a single `i64`-encoded column. `root_page_id`
+/// points at either a leaf (a tree with one page) or a branch page.
+pub struct Index {
+    pub root_page_id: PageID,
+}
+
+enum DescendStep {
+    Leaf(BTreeLeafPage),
+    Branch(BTreeBranchPage),
+}
+
+impl Index {
+    pub fn new(root_page_id: PageID) -> Self {
+        Index { root_page_id }
+    }
+    /// Descends from the root to the leaf that would hold `key`, following branch separator
+    /// keys at each level.
+    pub fn find_leaf(&self, key: i64, store: &mut impl PageStore) -> BTreeLeafPage {
+        let mut page_id = self.root_page_id;
+        loop {
+            match self.fetch(page_id, store) {
+                DescendStep::Leaf(leaf) => return leaf,
+                DescendStep::Branch(branch) => page_id = branch.find_child(key),
+            }
+        }
+    }
+    fn fetch(&self, page_id: PageID, store: &mut impl PageStore) -> DescendStep {
+        // Leaf and branch pages share the page-type byte at the front of every page (see
+        // `TABLE_PAGE_PAGE_TYPE` in `table_page.rs`); read through it to tell which one this is
+        // before asking the store for the right kind.
+        let leaf = store.fetch_leaf(page_id);
+        if page_type_of(&leaf.data) == BTREE_LEAF_PAGE_PAGE_TYPE.0 {
+            DescendStep::Leaf(leaf)
+        } else {
+            DescendStep::Branch(store.fetch_branch(page_id))
+        }
+    }
+    pub fn insert(&mut self, key: i64, rid: RID, store: &mut impl PageStore) -> Result<()> {
+        let mut path = Vec::new();
+        let mut page_id = self.root_page_id;
+        loop {
+            match self.fetch(page_id, store) {
+                DescendStep::Leaf(_) => break,
+                DescendStep::Branch(branch) => {
+                    let child = branch.find_child(key);
+                    path.push(branch);
+                    page_id = child;
+                }
+            }
+        }
+        let mut leaf = store.fetch_leaf(page_id);
+        if leaf.insert(key, rid.clone()).is_ok() {
+            store.write_leaf(&leaf);
+            return Ok(());
+        }
+
+        // The leaf is full: split it, then walk back up `path` pushing the separator key into
+        // each ancestor, splitting those too if they're also full. If the root itself splits, a
+        // new root branch is created above it.
+        let mut sibling = store.new_leaf();
+        let separator = leaf.split_into(&mut sibling);
+        let target = if key < separator { &mut leaf } else { &mut sibling };
+        target.insert(key, rid)?;
+        store.write_leaf(&leaf);
+        store.write_leaf(&sibling);
+
+        let mut separator_key = separator;
+        let mut right_child = sibling.page_id();
+        while let Some(mut branch) = path.pop() {
+            if branch.insert(separator_key, right_child).is_ok() {
+                store.write_branch(&branch);
+                return Ok(());
+            }
+            let mut branch_sibling = store.new_branch(branch.first_child_page_id());
+            let pushed_up = branch.split_into(&mut branch_sibling);
+            let target = if separator_key < pushed_up {
+                &mut branch
+            } else {
+                &mut branch_sibling
+            };
+            target.insert(separator_key, right_child)?;
+            store.write_branch(&branch);
+            store.write_branch(&branch_sibling);
+            separator_key = pushed_up;
+            right_child = branch_sibling.page_id();
+        }
+
+        let mut new_root = store.new_branch(self.root_page_id);
+        new_root.insert(separator_key, right_child)?;
+        store.write_branch(&new_root);
+        self.root_page_id = new_root.page_id();
+        Ok(())
+    }
+    pub fn delete(&mut self, key: i64, rid: &RID, store: &mut impl PageStore) -> bool {
+        let mut leaf = self.find_leaf(key, store);
+        let removed = leaf.delete(key, rid);
+        if removed {
+            store.write_leaf(&leaf);
+        }
+        removed
+    }
+    /// Walks leaf pages left to right via `next_page_id`, yielding every RID whose key falls in
+    /// `range`. Stops as soon as keys run past the range, since leaves are sorted.
+    pub fn scan(&self, range: &IndexRange, store: &mut impl PageStore) -> Vec<RID> {
+        let mut results = Vec::new();
+        let mut leaf = match range.lower_bound() {
+            // Descend by `key - 1` so a separator equal to `key` sends the search left: a leaf
+            // split can leave entries equal to the separator in the left leaf, and the walk
+            // along `next_page_id` below picks up the rest.
+            Some(key) if key > i64::MIN => self.find_leaf(key - 1, store),
+            _ => {
+                let mut page_id = self.root_page_id;
+                loop {
+                    match self.fetch(page_id, store) {
+                        DescendStep::Leaf(leaf) => break leaf,
+                        DescendStep::Branch(branch) => page_id = branch.first_child_page_id(),
+                    }
+                }
+            }
+        };
+        'leaves: loop {
+            for (key, rid) in leaf.entries() {
+                if range.is_past(key) {
+                    break 'leaves;
+                }
+                if range.contains(key) {
+                    results.push(rid);
+                }
+            }
+            let next_page_id = leaf.next_page_id();
+            if next_page_id == INVALID_PAGE_ID {
+                break;
+            }
+            leaf = store.fetch_leaf(next_page_id);
+        }
+        results
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{anyhow, Result};
+
+use crate::common::{PageID, INVALID_PAGE_ID, PAGE_SIZE};
+
+use super::{PageType, PAGE_ID_OFFSET, PAGE_ID_SIZE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE};
+
+pub const BTREE_BRANCH_PAGE_PAGE_TYPE: PageType = PageType(4);
+
+const NEXT_PAGE_ID_OFFSET: usize = PAGE_ID_OFFSET + PAGE_ID_SIZE;
+const NEXT_PAGE_ID_SIZE: usize = 4;
+const FIRST_CHILD_OFFSET: usize = NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE;
+const FIRST_CHILD_SIZE: usize = 4;
+const ENTRY_COUNT_OFFSET: usize = FIRST_CHILD_OFFSET + FIRST_CHILD_SIZE;
+const ENTRY_COUNT_SIZE: usize = 4;
+const HEADER_SIZE: usize = PAGE_TYPE_SIZE
+    + PAGE_ID_SIZE
+    + NEXT_PAGE_ID_SIZE
+    + FIRST_CHILD_SIZE
+    + ENTRY_COUNT_SIZE;
+
+const KEY_SIZE: usize = 8;
+const CHILD_PAGE_ID_SIZE: usize = 4;
+const ENTRY_SIZE: usize = KEY_SIZE + CHILD_PAGE_ID_SIZE;
+
+pub const MAX_BRANCH_ENTRIES: usize = (PAGE_SIZE - HEADER_SIZE) / ENTRY_SIZE;
+
+/// A branch (internal) page of a disk-backed B-tree index. Holds `entry_count` separator keys,
+/// each paired with the page id of the child subtree whose keys are >= it; `first_child_page_id`
+/// is the child for keys smaller than every separator. `next_page_id` is reserved for a sibling
+/// chain at the same level, mirroring `BTreeLeafPage`, though most lookups never need it.
+#[derive(Debug)]
+pub struct BTreeBranchPage {
+    pub data: Box<[u8]>,
+}
+
+impl BTreeBranchPage {
+    pub fn new(page_id: PageID, first_child_page_id: PageID) -> Self {
+        let mut data = vec![0u8; PAGE_SIZE];
+        data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]
+            .copy_from_slice(&BTREE_BRANCH_PAGE_PAGE_TYPE.0.to_le_bytes());
+        data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+        data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&INVALID_PAGE_ID.0.to_le_bytes());
+        data[FIRST_CHILD_OFFSET..(FIRST_CHILD_OFFSET + FIRST_CHILD_SIZE)]
+            .copy_from_slice(&first_child_page_id.0.to_le_bytes());
+        BTreeBranchPage { data: data.into() }
+    }
+    pub fn from_data(data: &[u8]) -> Self {
+        BTreeBranchPage { data: data.into() }
+    }
+    pub fn page_id(&self) -> PageID {
+        let mut bytes = [0u8; PAGE_ID_SIZE];
+        bytes.copy_from_slice(&self.data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]);
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn next_page_id(&self) -> PageID {
+        let mut bytes = [0u8; NEXT_PAGE_ID_SIZE];
+        bytes.copy_from_slice(
+            &self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)],
+        );
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn first_child_page_id(&self) -> PageID {
+        let mut bytes = [0u8; FIRST_CHILD_SIZE];
+        bytes.copy_from_slice(&self.data[FIRST_CHILD_OFFSET..(FIRST_CHILD_OFFSET + FIRST_CHILD_SIZE)]);
+        PageID(u32::from_le_bytes(bytes))
+    }
+    fn set_first_child_page_id(&mut self, page_id: PageID) {
+        self.data[FIRST_CHILD_OFFSET..(FIRST_CHILD_OFFSET + FIRST_CHILD_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+    }
+    pub fn entry_count(&self) -> usize {
+        let mut bytes = [0u8; ENTRY_COUNT_SIZE];
+        bytes.copy_from_slice(&self.data[ENTRY_COUNT_OFFSET..(ENTRY_COUNT_OFFSET + ENTRY_COUNT_SIZE)]);
+        u32::from_le_bytes(bytes) as usize
+    }
+    fn set_entry_count(&mut self, count: usize) {
+        self.data[ENTRY_COUNT_OFFSET..(ENTRY_COUNT_OFFSET + ENTRY_COUNT_SIZE)]
+            .copy_from_slice(&(count as u32).to_le_bytes());
+    }
+    pub fn is_full(&self) -> bool {
+        self.entry_count() >= MAX_BRANCH_ENTRIES
+    }
+    fn entry_offset(index: usize) -> usize {
+        HEADER_SIZE + index * ENTRY_SIZE
+    }
+    pub fn key_at(&self, index: usize) -> i64 {
+        let offset = Self::entry_offset(index);
+        let mut bytes = [0u8; KEY_SIZE];
+        bytes.copy_from_slice(&self.data[offset..(offset + KEY_SIZE)]);
+        i64::from_le_bytes(bytes)
+    }
+    pub fn child_at(&self, index: usize) -> PageID {
+        let offset = Self::entry_offset(index) + KEY_SIZE;
+        let mut bytes = [0u8; CHILD_PAGE_ID_SIZE];
+        bytes.copy_from_slice(&self.data[offset..(offset + CHILD_PAGE_ID_SIZE)]);
+        PageID(u32::from_le_bytes(bytes))
+    }
+    fn write_entry(&mut self, index: usize, key: i64, child_page_id: PageID) {
+        let offset = Self::entry_offset(index);
+        self.data[offset..(offset + KEY_SIZE)].copy_from_slice(&key.to_le_bytes());
+        self.data[(offset + KEY_SIZE)..(offset + ENTRY_SIZE)]
+            .copy_from_slice(&child_page_id.0.to_le_bytes());
+    }
+    /// Finds the child page id to descend into for `search_key`.
+    pub fn find_child(&self, search_key: i64) -> PageID {
+        let count = self.entry_count();
+        if count == 0 || search_key < self.key_at(0) {
+            return self.first_child_page_id();
+        }
+        let mut lo = 0;
+        let mut hi = count;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.key_at(mid) <= search_key {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        self.child_at(lo - 1)
+    }
+    /// Inserts a new separator `key` pointing at `child_page_id` (the subtree holding keys
+    /// >= `key`, previously part of the subtree to its left). Errors if the page is already
+    /// full; the caller (`Index::insert`) is responsible for splitting first.
+    pub fn insert(&mut self, key: i64, child_page_id: PageID) -> Result<()> {
+        if self.is_full() {
+            return Err(anyhow!("branch page is full"));
+        }
+        let count = self.entry_count();
+        let mut index = 0;
+        while index < count && self.key_at(index) < key {
+            index += 1;
+        }
+        for i in (index..count).rev() {
+            let (moved_key, moved_child) = (self.key_at(i), self.child_at(i));
+            self.write_entry(i + 1, moved_key, moved_child);
+        }
+        self.write_entry(index, key, child_page_id);
+        self.set_entry_count(count + 1);
+        Ok(())
+    }
+    /// Splits this (full) branch in half, moving the upper half of entries into `sibling`.
+    /// The median entry's key becomes the separator pushed up into the parent; its child
+    /// becomes `sibling`'s `first_child_page_id` rather than an entry in either half.
+    pub fn split_into(&mut self, sibling: &mut BTreeBranchPage) -> i64 {
+        let count = self.entry_count();
+        let mid = count / 2;
+        let median_key = self.key_at(mid);
+        sibling.set_first_child_page_id(self.child_at(mid));
+        for i in (mid + 1)..count {
+            let (key, child) = (self.key_at(i), self.child_at(i));
+            sibling.write_entry(i - mid - 1, key, child);
+        }
+        sibling.set_entry_count(count - mid - 1);
+        self.set_entry_count(mid);
+        median_key
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Result};
+
+use crate::{
+    common::{PageID, INVALID_PAGE_ID, PAGE_SIZE},
+    tuple::RID,
+};
+
+use super::{PageType, PAGE_ID_OFFSET, PAGE_ID_SIZE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE};
+
+pub const BTREE_LEAF_PAGE_PAGE_TYPE: PageType = PageType(3);
+
+const NEXT_PAGE_ID_OFFSET: usize = PAGE_ID_OFFSET + PAGE_ID_SIZE;
+const NEXT_PAGE_ID_SIZE: usize = 4;
+const ENTRY_COUNT_OFFSET: usize = NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE;
+const ENTRY_COUNT_SIZE: usize = 4;
+const HEADER_SIZE: usize = PAGE_TYPE_SIZE + PAGE_ID_SIZE + NEXT_PAGE_ID_SIZE + ENTRY_COUNT_SIZE;
+
+const KEY_SIZE: usize = 8;
+const RID_PAGE_ID_SIZE: usize = 4;
+const RID_SLOT_SIZE: usize = 4;
+const ENTRY_SIZE: usize = KEY_SIZE + RID_PAGE_ID_SIZE + RID_SLOT_SIZE;
+
+pub const MAX_LEAF_ENTRIES: usize = (PAGE_SIZE - HEADER_SIZE) / ENTRY_SIZE;
+
+/// A leaf page of a disk-backed B-tree index: entries are kept sorted by key so lookups can
+/// binary search within the page and range scans can walk `next_page_id` across siblings.
+#[derive(Debug)]
+pub struct BTreeLeafPage {
+    pub data: Box<[u8]>,
+}
+
+impl BTreeLeafPage {
+    pub fn new(page_id: PageID) -> Self {
+        let mut data = vec![0u8; PAGE_SIZE];
+        data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]
+            .copy_from_slice(&BTREE_LEAF_PAGE_PAGE_TYPE.0.to_le_bytes());
+        data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+        data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&INVALID_PAGE_ID.0.to_le_bytes());
+        BTreeLeafPage { data: data.into() }
+    }
+    pub fn from_data(data: &[u8]) -> Self {
+        BTreeLeafPage { data: data.into() }
+    }
+    pub fn page_id(&self) -> PageID {
+        let mut bytes = [0u8; PAGE_ID_SIZE];
+        bytes.copy_from_slice(&self.data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]);
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn next_page_id(&self) -> PageID {
+        let mut bytes = [0u8; NEXT_PAGE_ID_SIZE];
+        bytes.copy_from_slice(
+            &self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)],
+        );
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn set_next_page_id(&mut self, page_id: PageID) {
+        self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+    }
+    pub fn entry_count(&self) -> usize {
+        let mut bytes = [0u8; ENTRY_COUNT_SIZE];
+        bytes.copy_from_slice(&self.data[ENTRY_COUNT_OFFSET..(ENTRY_COUNT_OFFSET + ENTRY_COUNT_SIZE)]);
+        u32::from_le_bytes(bytes) as usize
+    }
+    fn set_entry_count(&mut self, count: usize) {
+        self.data[ENTRY_COUNT_OFFSET..(ENTRY_COUNT_OFFSET + ENTRY_COUNT_SIZE)]
+            .copy_from_slice(&(count as u32).to_le_bytes());
+    }
+    pub fn is_full(&self) -> bool {
+        self.entry_count() >= MAX_LEAF_ENTRIES
+    }
+    fn entry_offset(index: usize) -> usize {
+        HEADER_SIZE + index * ENTRY_SIZE
+    }
+    pub fn key_at(&self, index: usize) -> i64 {
+        let offset = Self::entry_offset(index);
+        let mut bytes = [0u8; KEY_SIZE];
+        bytes.copy_from_slice(&self.data[offset..(offset + KEY_SIZE)]);
+        i64::from_le_bytes(bytes)
+    }
+    pub fn rid_at(&self, index: usize) -> RID {
+        let offset = Self::entry_offset(index) + KEY_SIZE;
+        let mut page_id_bytes = [0u8; RID_PAGE_ID_SIZE];
+        page_id_bytes.copy_from_slice(&self.data[offset..(offset + RID_PAGE_ID_SIZE)]);
+        let mut slot_bytes = [0u8; RID_SLOT_SIZE];
+        slot_bytes.copy_from_slice(
+            &self.data[(offset + RID_PAGE_ID_SIZE)..(offset + RID_PAGE_ID_SIZE + RID_SLOT_SIZE)],
+        );
+        RID {
+            page_id: PageID(u32::from_le_bytes(page_id_bytes)),
+            slot: u32::from_le_bytes(slot_bytes),
+        }
+    }
+    pub fn entries(&self) -> Vec<(i64, RID)> {
+        (0..self.entry_count())
+            .map(|i| (self.key_at(i), self.rid_at(i)))
+            .collect()
+    }
+    /// Returns the index of the first entry with a key >= `key`, i.e. where `key` would be
+    /// inserted to keep the page sorted.
+    pub fn lower_bound(&self, key: i64) -> usize {
+        let count = self.entry_count();
+        let mut lo = 0;
+        let mut hi = count;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.key_at(mid) < key {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+    fn write_entry(&mut self, index: usize, key: i64, rid: &RID) {
+        let offset = Self::entry_offset(index);
+        self.data[offset..(offset + KEY_SIZE)].copy_from_slice(&key.to_le_bytes());
+        self.data[(offset + KEY_SIZE)..(offset + KEY_SIZE + RID_PAGE_ID_SIZE)]
+            .copy_from_slice(&rid.page_id.0.to_le_bytes());
+        self.data[(offset + KEY_SIZE + RID_PAGE_ID_SIZE)..(offset + ENTRY_SIZE)]
+            .copy_from_slice(&rid.slot.to_le_bytes());
+    }
+    /// Inserts `(key, rid)` in sorted order. Errors if the page is already full; the caller
+    /// (`Index::insert`) is responsible for splitting first.
+    pub fn insert(&mut self, key: i64, rid: RID) -> Result<()> {
+        if self.is_full() {
+            return Err(anyhow!("leaf page is full"));
+        }
+        let count = self.entry_count();
+        let index = self.lower_bound(key);
+        for i in (index..count).rev() {
+            let (moved_key, moved_rid) = (self.key_at(i), self.rid_at(i));
+            self.write_entry(i + 1, moved_key, &moved_rid);
+        }
+        self.write_entry(index, key, &rid);
+        self.set_entry_count(count + 1);
+        Ok(())
+    }
+    /// Removes the first entry matching `(key, rid)`, if any. Returns whether an entry was
+    /// removed.
+    pub fn delete(&mut self, key: i64, rid: &RID) -> bool {
+        let count = self.entry_count();
+        let Some(index) = (0..count)
+            .find(|&i| self.key_at(i) == key && self.rid_at(i).page_id == rid.page_id && self.rid_at(i).slot == rid.slot)
+        else {
+            return false;
+        };
+        for i in index..(count - 1) {
+            let (moved_key, moved_rid) = (self.key_at(i + 1), self.rid_at(i + 1));
+            self.write_entry(i, moved_key, &moved_rid);
+        }
+        self.set_entry_count(count - 1);
+        true
+    }
+    /// Splits this (full) leaf in half, moving the upper half of entries into `sibling` and
+    /// chaining `next_page_id` so range scans keep walking forward. Returns the separator key
+    /// (the sibling's first key) to push up into the parent branch.
+    pub fn split_into(&mut self, sibling: &mut BTreeLeafPage) -> i64 {
+        let count = self.entry_count();
+        let mid = count / 2;
+        for i in mid..count {
+            let (key, rid) = (self.key_at(i), self.rid_at(i));
+            sibling.write_entry(i - mid, key, &rid);
+        }
+        sibling.set_entry_count(count - mid);
+        self.set_entry_count(mid);
+        sibling.set_next_page_id(self.next_page_id());
+        self.set_next_page_id(sibling.page_id());
+        sibling.key_at(0)
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Result};
+
+use crate::common::{PageID, INVALID_PAGE_ID, PAGE_SIZE};
+
+use super::{PageType, PAGE_ID_OFFSET, PAGE_ID_SIZE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE};
+
+pub const OVERFLOW_PAGE_PAGE_TYPE: PageType = PageType(2);
+
+const NEXT_PAGE_ID_OFFSET: usize = PAGE_ID_OFFSET + PAGE_ID_SIZE;
+const NEXT_PAGE_ID_SIZE: usize = 4;
+const LEN_OFFSET: usize = NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE;
+const LEN_SIZE: usize = 4;
+const HEADER_SIZE: usize = PAGE_TYPE_SIZE + PAGE_ID_SIZE + NEXT_PAGE_ID_SIZE + LEN_SIZE;
+const DATA_OFFSET: usize = HEADER_SIZE;
+
+/// A page of raw byte runs chained off a `TablePage` line pointer to hold a tuple too large to
+/// fit on a single table page. The line pointer stores only the first overflow page's id and
+/// the tuple's total length; none of the tuple's bytes are kept inline. Each overflow page
+/// points at the next via `next_page_id` until the chain's lengths sum to the tuple's total
+/// length.
+#[derive(Debug)]
+pub struct OverflowPage {
+    pub data: Box<[u8]>,
+}
+
+impl OverflowPage {
+    pub fn new(page_id: PageID) -> Self {
+        let mut data = vec![0u8; PAGE_SIZE];
+        data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]
+            .copy_from_slice(&OVERFLOW_PAGE_PAGE_TYPE.0.to_le_bytes());
+        data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+        data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&INVALID_PAGE_ID.0.to_le_bytes());
+        OverflowPage { data: data.into() }
+    }
+    pub fn from_data(data: &[u8]) -> Self {
+        OverflowPage { data: data.into() }
+    }
+    /// Maximum number of payload bytes a single overflow page can hold.
+    pub fn capacity() -> usize {
+        PAGE_SIZE - HEADER_SIZE
+    }
+    /// Stores `bytes` as this page's payload run. `bytes.len()` must not exceed `capacity()`.
+    pub fn set_payload(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.len() > Self::capacity() {
+            return Err(anyhow!("overflow page payload exceeds page capacity"));
+        }
+        self.data[LEN_OFFSET..(LEN_OFFSET + LEN_SIZE)]
+            .copy_from_slice(&(bytes.len() as u32).to_le_bytes());
+        self.data[DATA_OFFSET..(DATA_OFFSET + bytes.len())].copy_from_slice(bytes);
+        Ok(())
+    }
+    pub fn payload(&self) -> &[u8] {
+        let len = self.payload_len();
+        &self.data[DATA_OFFSET..(DATA_OFFSET + len)]
+    }
+    pub fn payload_len(&self) -> usize {
+        let mut bytes = [0u8; LEN_SIZE];
+        bytes.copy_from_slice(&self.data[LEN_OFFSET..(LEN_OFFSET + LEN_SIZE)]);
+        u32::from_le_bytes(bytes) as usize
+    }
+    pub fn page_id(&self) -> PageID {
+        let mut bytes = [0u8; PAGE_ID_SIZE];
+        bytes.copy_from_slice(&self.data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]);
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn next_page_id(&self) -> PageID {
+        let mut bytes = [0u8; NEXT_PAGE_ID_SIZE];
+        bytes.copy_from_slice(
+            &self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)],
+        );
+        PageID(u32::from_le_bytes(bytes))
+    }
+    pub fn set_next_page_id(&mut self, page_id: PageID) {
+        self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&page_id.0.to_le_bytes());
+    }
+}
@@ -1,152 +1,229 @@
 use anyhow::{anyhow, Result};
 
 use crate::{
-    common::{PageID, TransactionID, INVALID_PAGE_ID, LSN, PAGE_SIZE},
+    common::{PageID, TransactionID, INVALID_PAGE_ID, INVALID_TRANSACTION_ID, LSN, PAGE_SIZE},
     tuple::Tuple,
 };
 
-use super::{PageType, PAGE_ID_OFFSET, PAGE_ID_SIZE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE};
+use super::{
+    overflow_page::OverflowPage,
+    table_page_codec::{TablePageCodec, HEADER_LEN, LINE_POINTER_SIZE},
+    PageType,
+};
 
 pub const TABLE_PAGE_PAGE_TYPE: PageType = PageType(1);
 
-const LSN_OFFSET: usize = PAGE_ID_OFFSET + PAGE_ID_SIZE;
-const LSN_SIZE: usize = 8;
-const NEXT_PAGE_ID_OFFSET: usize = LSN_OFFSET + LSN_SIZE;
-const NEXT_PAGE_ID_SIZE: usize = 4;
-const LOWER_OFFSET_OFFSET: usize = NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE;
-const LOWER_OFFSET_SIZE: usize = 4;
-const UPPER_OFFSET_OFFSET: usize = LOWER_OFFSET_OFFSET + LOWER_OFFSET_SIZE;
-const UPPER_OFFSET_SIZE: usize = 4;
-const HEADER_SIZE: usize = PAGE_TYPE_SIZE
-    + PAGE_ID_SIZE
-    + LSN_SIZE
-    + NEXT_PAGE_ID_SIZE
-    + LOWER_OFFSET_SIZE
-    + UPPER_OFFSET_SIZE;
-const LINE_POINTER_OFFSET_SIZE: usize = 4;
-const LINE_POINTER_SIZE_SIZE: usize = 4;
-const LINE_POINTER_SIZE: usize = LINE_POINTER_OFFSET_SIZE + LINE_POINTER_SIZE_SIZE;
+/// High bit of a line pointer's size word: marks the slot as pointing at an overflow chain
+/// rather than an inline tuple, since tuples can outgrow a single page.
+pub(super) const OVERFLOW_FLAG: u32 = 1 << 31;
+
+/// What a line pointer resolves to: the tuple's bytes directly, or (for a tuple too large to
+/// fit on one page) the location of the overflow chain holding them.
+#[derive(Debug, Clone)]
+pub enum TupleSlot {
+    Inline(Box<[u8]>),
+    Overflow { first_page_id: PageID, total_len: usize },
+}
+
+impl TupleSlot {
+    /// Reassembles the full tuple bytes, walking the overflow chain via `fetch_overflow_page`
+    /// when needed. `fetch_overflow_page` is expected to defer to the buffer pool, since
+    /// `TablePage` itself has no way to read other pages.
+    pub fn reassemble(&self, mut fetch_overflow_page: impl FnMut(PageID) -> OverflowPage) -> Box<[u8]> {
+        match self {
+            TupleSlot::Inline(bytes) => bytes.clone(),
+            TupleSlot::Overflow {
+                first_page_id,
+                total_len,
+            } => {
+                let mut result = Vec::with_capacity(*total_len);
+                let mut page_id = *first_page_id;
+                while result.len() < *total_len {
+                    let page = fetch_overflow_page(page_id);
+                    result.extend_from_slice(page.payload());
+                    page_id = page.next_page_id();
+                }
+                result.truncate(*total_len);
+                result.into()
+            }
+        }
+    }
+}
+
+/// A line pointer slot, decoded: either a live tuple or a tombstone left behind by `vacuum` to
+/// keep later slot indices stable (a slot index is part of a tuple's RID).
+#[derive(Debug, Clone)]
+pub(super) enum Slot {
+    Tuple(TupleSlot),
+    Tombstone,
+}
 
+/// A table page's contents, decoded from its on-disk byte layout by `TablePageCodec`. Every
+/// method here works in terms of these plain fields; none of them touch a byte offset directly
+/// — that's entirely `TablePageCodec`'s job, so a future layout change only needs a new
+/// `decode_v*` arm there instead of touching every accessor.
 #[derive(Debug)]
 pub struct TablePage {
-    pub data: Box<[u8]>,
+    pub(super) page_id: PageID,
+    pub(super) lsn: LSN,
+    pub(super) next_page_id: PageID,
+    pub(super) slots: Vec<Slot>,
 }
 
 impl TablePage {
     pub fn new(page_id: PageID) -> Self {
-        let mut data = vec![0u8; PAGE_SIZE];
-        data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]
-            .copy_from_slice(&TABLE_PAGE_PAGE_TYPE.0.to_le_bytes());
-        data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]
-            .copy_from_slice(&page_id.0.to_le_bytes());
-        data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
-            .copy_from_slice(&INVALID_PAGE_ID.0.to_le_bytes());
-        data[LOWER_OFFSET_OFFSET..(LOWER_OFFSET_OFFSET + LOWER_OFFSET_SIZE)]
-            .copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
-        data[UPPER_OFFSET_OFFSET..(UPPER_OFFSET_OFFSET + UPPER_OFFSET_SIZE)]
-            .copy_from_slice(&(PAGE_SIZE as u32).to_le_bytes());
-        TablePage { data: data.into() }
-    }
-    pub fn from_data(data: &[u8]) -> Self {
-        TablePage { data: data.into() }
+        TablePage {
+            page_id,
+            lsn: LSN(0),
+            next_page_id: INVALID_PAGE_ID,
+            slots: Vec::new(),
+        }
+    }
+    pub fn from_data(data: &[u8]) -> Result<Self> {
+        TablePageCodec::decode(data)
     }
+    pub fn to_bytes(&self) -> Box<[u8]> {
+        TablePageCodec::encode(self)
+    }
+    /// Inserts `data` inline. Errors if it doesn't fit in this page's free space, including the
+    /// case where `data` is too large for any single empty page; callers that need to store a
+    /// tuple wider than a page should instead chain it across `OverflowPage`s and record the
+    /// chain's head with `insert_overflow`.
     pub fn insert(&mut self, data: &[u8]) -> Result<()> {
-        // TODO: too large for one page
         if self.free_space() < data.len() + LINE_POINTER_SIZE {
             return Err(anyhow!("free space not enough"));
         }
-
-        let data_size = data.len() as u32;
-        let lower_offset = self.lower_offset();
-        let upper_offset = self.upper_offset();
-        let next_lower_offset: u32 = lower_offset + LINE_POINTER_SIZE as u32;
-        let next_upper_offset: u32 = upper_offset - data.len() as u32;
-        self.data[LOWER_OFFSET_OFFSET..(LOWER_OFFSET_OFFSET + LOWER_OFFSET_SIZE)]
-            .copy_from_slice(&next_lower_offset.to_le_bytes());
-        self.data[UPPER_OFFSET_OFFSET..(UPPER_OFFSET_OFFSET + UPPER_OFFSET_SIZE)]
-            .copy_from_slice(&next_upper_offset.to_le_bytes());
-        self.data[(lower_offset as usize)..(lower_offset as usize + LINE_POINTER_OFFSET_SIZE)]
-            .copy_from_slice(&next_upper_offset.to_le_bytes());
-        self.data[((lower_offset as usize) + LINE_POINTER_OFFSET_SIZE)
-            ..((lower_offset as usize) + LINE_POINTER_SIZE)]
-            .copy_from_slice(&data_size.to_le_bytes());
-        self.data[(next_upper_offset as usize)..(upper_offset as usize)].copy_from_slice(data);
-
+        self.slots.push(Slot::Tuple(TupleSlot::Inline(data.into())));
         Ok(())
     }
-    pub fn delete(&mut self, index: u32, txn_id: TransactionID) {
-        let offset = self.line_pointer_offset(index as usize) as usize;
-        let size = self.line_pointer_size(index as usize) as usize;
-        let mut tuple = Tuple::new(None, &self.data[offset..(offset + size)]);
-        tuple.set_xmax(txn_id);
-        self.data[offset..(offset + size)].copy_from_slice(&tuple.data);
+    /// Records a line pointer for a tuple that was chained across overflow pages instead of
+    /// stored inline, pointing at the chain's first page and the tuple's total length. Unlike
+    /// `insert`, this only consumes a line pointer slot; the tuple bytes themselves live in the
+    /// overflow chain.
+    pub fn insert_overflow(&mut self, first_page_id: PageID, total_len: usize) -> Result<()> {
+        if self.free_space() < LINE_POINTER_SIZE {
+            return Err(anyhow!("free space not enough"));
+        }
+        if total_len as u32 & OVERFLOW_FLAG != 0 {
+            return Err(anyhow!("tuple too large to represent"));
+        }
+        self.slots.push(Slot::Tuple(TupleSlot::Overflow {
+            first_page_id,
+            total_len,
+        }));
+        Ok(())
     }
+    /// Stamps `xmax` on an inline tuple. Overflow tuples carry their header on the first page of
+    /// their chain instead, so this errors on one rather than silently doing nothing; callers
+    /// must delete those by freeing the chain through the buffer pool and then calling
+    /// `free_overflow_slot` — use `get_tuple_slot` to tell the two cases apart first.
+    pub fn delete(&mut self, index: u32, txn_id: TransactionID) -> Result<()> {
+        match &mut self.slots[index as usize] {
+            Slot::Tuple(TupleSlot::Inline(bytes)) => {
+                let mut tuple = Tuple::new(None, &bytes[..]);
+                tuple.set_xmax(txn_id);
+                *bytes = tuple.data.into();
+                Ok(())
+            }
+            Slot::Tuple(TupleSlot::Overflow { .. }) => Err(anyhow!(
+                "slot {index} holds an overflow tuple; free its chain and call free_overflow_slot instead of delete"
+            )),
+            Slot::Tombstone => Ok(()),
+        }
+    }
+    /// Replaces an overflow slot with a tombstone once the caller has freed its chain through
+    /// the buffer pool (see `delete`). Slot indices stay stable the same way `vacuum` keeps them
+    /// stable for a dead inline tuple, since a slot index is part of a tuple's RID.
+    pub fn free_overflow_slot(&mut self, index: u32) {
+        self.slots[index as usize] = Slot::Tombstone;
+    }
+    /// Resolves a line pointer to either its inline tuple bytes or the location of its
+    /// overflow chain (see `TupleSlot`). Panics if the slot is a tombstone.
+    pub fn get_tuple_slot(&self, index: usize) -> TupleSlot {
+        match &self.slots[index] {
+            Slot::Tuple(slot) => slot.clone(),
+            Slot::Tombstone => panic!("slot {index} is a tombstone"),
+        }
+    }
+    /// Discards tuples whose `xmax` is older than `oldest_active_txn`, replacing them with
+    /// tombstones.
+    ///
+    /// Slot indices are part of a tuple's RID, so a dead slot is left behind as a tombstone
+    /// rather than shifting later slots down; only a run of tombstones at the *end* of the slot
+    /// array is actually dropped. Returns the number of bytes of free space reclaimed.
+    pub fn vacuum(&mut self, oldest_active_txn: TransactionID) -> usize {
+        let free_space_before = self.free_space();
+        for slot in &mut self.slots {
+            if let Slot::Tuple(TupleSlot::Inline(bytes)) = slot {
+                let tuple = Tuple::new(None, &bytes[..]);
+                let xmax = tuple.xmax();
+                if xmax != INVALID_TRANSACTION_ID && xmax < oldest_active_txn {
+                    *slot = Slot::Tombstone;
+                }
+            }
+        }
+        while matches!(self.slots.last(), Some(Slot::Tombstone)) {
+            self.slots.pop();
+        }
+        self.free_space() - free_space_before
+    }
+    /// Convenience wrapper over `get_tuple_slot` for pages known not to contain overflow
+    /// tuples; panics otherwise, since reassembling those requires the buffer pool. Skips
+    /// tombstones left behind by `vacuum`.
     pub fn get_tuples(&self) -> Vec<Box<[u8]>> {
-        let count = self.tuple_count();
-        (0..count).map(|i| self.get_tuple(i)).collect()
+        (0..self.slots.len())
+            .filter(|&index| !matches!(self.slots[index], Slot::Tombstone))
+            .map(|index| self.get_tuple(index))
+            .collect()
     }
     pub fn get_tuple(&self, index: usize) -> Box<[u8]> {
-        let offset = self.line_pointer_offset(index) as usize;
-        let size = self.line_pointer_size(index) as usize;
-        self.data[offset..(offset + size)].into()
+        match self.get_tuple_slot(index) {
+            TupleSlot::Inline(bytes) => bytes,
+            TupleSlot::Overflow { .. } => panic!(
+                "tuple at slot {index} is stored out-of-line; use get_tuple_slot and reassemble via the buffer pool"
+            ),
+        }
+    }
+    /// Like `get_tuples`, but resolves overflow tuples via `fetch_overflow_page` instead of
+    /// panicking on them (see `TupleSlot::reassemble`). Callers that can reach the buffer pool —
+    /// e.g. a full-table scan — should use this instead of `get_tuples` for any page that might
+    /// hold an overflow tuple.
+    pub fn get_tuples_with(
+        &self,
+        mut fetch_overflow_page: impl FnMut(PageID) -> OverflowPage,
+    ) -> Vec<Box<[u8]>> {
+        (0..self.slots.len())
+            .filter(|&index| !matches!(self.slots[index], Slot::Tombstone))
+            .map(|index| self.get_tuple_slot(index).reassemble(&mut fetch_overflow_page))
+            .collect()
     }
     pub fn tuple_count(&self) -> usize {
-        let lower_offset = self.lower_offset();
-        (lower_offset as usize - HEADER_SIZE) / LINE_POINTER_SIZE
+        self.slots.len()
     }
     pub fn page_id(&self) -> PageID {
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(&self.data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]);
-        PageID(u32::from_le_bytes(bytes))
+        self.page_id
     }
     pub fn lsn(&self) -> LSN {
-        let mut bytes = [0u8; 8];
-        bytes.copy_from_slice(&self.data[LSN_OFFSET..(LSN_OFFSET + LSN_SIZE)]);
-        LSN(u64::from_le_bytes(bytes))
+        self.lsn
     }
     pub fn next_page_id(&self) -> PageID {
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(
-            &self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)],
-        );
-        PageID(u32::from_le_bytes(bytes))
+        self.next_page_id
     }
     pub fn set_lsn(&mut self, lsn: LSN) {
-        self.data[LSN_OFFSET..(LSN_OFFSET + LSN_SIZE)].copy_from_slice(&lsn.0.to_le_bytes());
+        self.lsn = lsn;
     }
     pub fn set_next_page_id(&mut self, page_id: PageID) {
-        self.data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
-            .copy_from_slice(&page_id.0.to_le_bytes());
+        self.next_page_id = page_id;
     }
     fn free_space(&self) -> usize {
-        let lower_offset = self.lower_offset();
-        let upper_offset = self.upper_offset();
-        (upper_offset - lower_offset) as usize
-    }
-    fn lower_offset(&self) -> u32 {
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(
-            &self.data[LOWER_OFFSET_OFFSET..(LOWER_OFFSET_OFFSET + LOWER_OFFSET_SIZE)],
-        );
-        u32::from_le_bytes(bytes)
-    }
-    fn upper_offset(&self) -> u32 {
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(
-            &self.data[UPPER_OFFSET_OFFSET..(UPPER_OFFSET_OFFSET + UPPER_OFFSET_SIZE)],
-        );
-        u32::from_le_bytes(bytes)
-    }
-    fn line_pointer_offset(&self, index: usize) -> u32 {
-        let offset = HEADER_SIZE + index * LINE_POINTER_SIZE;
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(&self.data[offset..(offset + LINE_POINTER_OFFSET_SIZE)]);
-        u32::from_le_bytes(bytes)
-    }
-    fn line_pointer_size(&self, index: usize) -> u32 {
-        let offset = HEADER_SIZE + index * LINE_POINTER_SIZE + LINE_POINTER_OFFSET_SIZE;
-        let mut bytes = [0u8; 4];
-        bytes.copy_from_slice(&self.data[offset..(offset + LINE_POINTER_SIZE_SIZE)]);
-        u32::from_le_bytes(bytes)
+        let used: usize = self
+            .slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Tuple(TupleSlot::Inline(bytes)) => bytes.len(),
+                Slot::Tuple(TupleSlot::Overflow { .. }) | Slot::Tombstone => 0,
+            })
+            .sum();
+        PAGE_SIZE - HEADER_LEN - self.slots.len() * LINE_POINTER_SIZE - used
     }
 }
@@ -0,0 +1,197 @@
+use anyhow::{anyhow, Result};
+
+use crate::common::{PageID, LSN, PAGE_SIZE};
+
+use super::{
+    table_page::{Slot, TablePage, TupleSlot, OVERFLOW_FLAG, TABLE_PAGE_PAGE_TYPE},
+    PAGE_ID_OFFSET, PAGE_ID_SIZE, PAGE_TYPE_OFFSET, PAGE_TYPE_SIZE,
+};
+
+/// Bumped whenever the on-disk layout changes; stamped into every encoded page so `decode` can
+/// tell which layout it's reading instead of guessing from the bytes. `encode` always writes
+/// `CURRENT_FORMAT_VERSION`; old versions only need a `decode_v*` arm, never an `encode_v*` one.
+pub const CURRENT_FORMAT_VERSION: u8 = 1;
+
+// Placed directly after the shared page_type/page_id prefix — and before everything else,
+// including LSN — so the digest covers the entire rest of the page (every header field other
+// than the identity prefix and the checksum itself) instead of leaving LSN unprotected.
+const CHECKSUM_OFFSET: usize = PAGE_ID_OFFSET + PAGE_ID_SIZE;
+const CHECKSUM_SIZE: usize = 8;
+// The hash region starts here, i.e. just past the checksum bytes, so the digest never covers itself.
+const CHECKSUM_END: usize = CHECKSUM_OFFSET + CHECKSUM_SIZE;
+const LSN_OFFSET: usize = CHECKSUM_END;
+const LSN_SIZE: usize = 8;
+const VERSION_OFFSET: usize = LSN_OFFSET + LSN_SIZE;
+const VERSION_SIZE: usize = 1;
+const NEXT_PAGE_ID_OFFSET: usize = VERSION_OFFSET + VERSION_SIZE;
+const NEXT_PAGE_ID_SIZE: usize = 4;
+const SLOT_COUNT_OFFSET: usize = NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE;
+const SLOT_COUNT_SIZE: usize = 4;
+const HEADER_SIZE: usize = PAGE_TYPE_SIZE
+    + PAGE_ID_SIZE
+    + LSN_SIZE
+    + CHECKSUM_SIZE
+    + VERSION_SIZE
+    + NEXT_PAGE_ID_SIZE
+    + SLOT_COUNT_SIZE;
+
+const LINE_POINTER_OFFSET_SIZE: usize = 4;
+const LINE_POINTER_SIZE_SIZE: usize = 4;
+pub(super) const LINE_POINTER_SIZE: usize = LINE_POINTER_OFFSET_SIZE + LINE_POINTER_SIZE_SIZE;
+pub(super) const HEADER_LEN: usize = HEADER_SIZE;
+
+/// CRC-64/XZ, computed bit-by-bit since pages are small and this only runs on page in/out.
+fn crc64(data: &[u8]) -> u64 {
+    const POLY: u64 = 0xc96c5795d7870f42;
+    let mut crc = !0u64;
+    for &byte in data {
+        crc ^= byte as u64;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Converts between the decoded, layout-agnostic `TablePage` the rest of the codebase works
+/// with and the versioned byte layout the buffer pool reads and writes. Keeping this separate
+/// from `TablePage` means a future layout change only needs a new `decode_v*` arm here; every
+/// accessor and mutator elsewhere in `table_page.rs` operates on plain Rust fields and never
+/// touches an offset.
+pub struct TablePageCodec;
+
+impl TablePageCodec {
+    /// Always writes the current format version and line-pointer layout, regardless of what
+    /// version the page was last decoded from.
+    pub fn encode(page: &TablePage) -> Box<[u8]> {
+        let mut data = vec![0u8; PAGE_SIZE];
+        data[PAGE_TYPE_OFFSET..(PAGE_TYPE_OFFSET + PAGE_TYPE_SIZE)]
+            .copy_from_slice(&TABLE_PAGE_PAGE_TYPE.0.to_le_bytes());
+        data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]
+            .copy_from_slice(&page.page_id.0.to_le_bytes());
+        data[LSN_OFFSET..(LSN_OFFSET + LSN_SIZE)].copy_from_slice(&page.lsn.0.to_le_bytes());
+        data[VERSION_OFFSET] = CURRENT_FORMAT_VERSION;
+        data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)]
+            .copy_from_slice(&page.next_page_id.0.to_le_bytes());
+        data[SLOT_COUNT_OFFSET..(SLOT_COUNT_OFFSET + SLOT_COUNT_SIZE)]
+            .copy_from_slice(&(page.slots.len() as u32).to_le_bytes());
+
+        let mut upper_offset = PAGE_SIZE as u32;
+        for (index, slot) in page.slots.iter().enumerate() {
+            let line_pointer_offset = HEADER_SIZE + index * LINE_POINTER_SIZE;
+            match slot {
+                Slot::Tombstone => {
+                    // Leave the zeroed offset/size in place; `decode` reads that back as a tombstone.
+                }
+                Slot::Tuple(TupleSlot::Inline(bytes)) => {
+                    let new_offset = upper_offset - bytes.len() as u32;
+                    data[(new_offset as usize)..(upper_offset as usize)].copy_from_slice(bytes);
+                    data[line_pointer_offset..(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)]
+                        .copy_from_slice(&new_offset.to_le_bytes());
+                    data[(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)
+                        ..(line_pointer_offset + LINE_POINTER_SIZE)]
+                        .copy_from_slice(&(bytes.len() as u32).to_le_bytes());
+                    upper_offset = new_offset;
+                }
+                Slot::Tuple(TupleSlot::Overflow {
+                    first_page_id,
+                    total_len,
+                }) => {
+                    data[line_pointer_offset..(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)]
+                        .copy_from_slice(&first_page_id.0.to_le_bytes());
+                    data[(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)
+                        ..(line_pointer_offset + LINE_POINTER_SIZE)]
+                        .copy_from_slice(&(*total_len as u32 | OVERFLOW_FLAG).to_le_bytes());
+                }
+            }
+        }
+
+        let checksum = crc64(&data[CHECKSUM_END..]);
+        data[CHECKSUM_OFFSET..CHECKSUM_END].copy_from_slice(&checksum.to_le_bytes());
+        data.into()
+    }
+    /// Verifies the checksum, then dispatches on the format-version byte to decode the rest of
+    /// the header and slot array. Rejects the page instead of handing back garbage if either
+    /// check fails.
+    pub fn decode(data: &[u8]) -> Result<TablePage> {
+        let mut checksum_bytes = [0u8; CHECKSUM_SIZE];
+        checksum_bytes.copy_from_slice(&data[CHECKSUM_OFFSET..CHECKSUM_END]);
+        let stored_checksum = u64::from_le_bytes(checksum_bytes);
+        let computed_checksum = crc64(&data[CHECKSUM_END..]);
+        if stored_checksum != computed_checksum {
+            return Err(anyhow!(
+                "checksum mismatch on page: stored {:#x}, computed {:#x}",
+                stored_checksum,
+                computed_checksum
+            ));
+        }
+
+        match data[VERSION_OFFSET] {
+            1 => Self::decode_v1(data),
+            other => Err(anyhow!("unsupported table page format version {other}")),
+        }
+    }
+    fn decode_v1(data: &[u8]) -> Result<TablePage> {
+        let mut page_id_bytes = [0u8; PAGE_ID_SIZE];
+        page_id_bytes.copy_from_slice(&data[PAGE_ID_OFFSET..(PAGE_ID_OFFSET + PAGE_ID_SIZE)]);
+        let page_id = PageID(u32::from_le_bytes(page_id_bytes));
+
+        let mut lsn_bytes = [0u8; LSN_SIZE];
+        lsn_bytes.copy_from_slice(&data[LSN_OFFSET..(LSN_OFFSET + LSN_SIZE)]);
+        let lsn = LSN(u64::from_le_bytes(lsn_bytes));
+
+        let mut next_page_id_bytes = [0u8; NEXT_PAGE_ID_SIZE];
+        next_page_id_bytes.copy_from_slice(
+            &data[NEXT_PAGE_ID_OFFSET..(NEXT_PAGE_ID_OFFSET + NEXT_PAGE_ID_SIZE)],
+        );
+        let next_page_id = PageID(u32::from_le_bytes(next_page_id_bytes));
+
+        let mut slot_count_bytes = [0u8; SLOT_COUNT_SIZE];
+        slot_count_bytes
+            .copy_from_slice(&data[SLOT_COUNT_OFFSET..(SLOT_COUNT_OFFSET + SLOT_COUNT_SIZE)]);
+        let slot_count = u32::from_le_bytes(slot_count_bytes) as usize;
+
+        let mut slots = Vec::with_capacity(slot_count);
+        for index in 0..slot_count {
+            let line_pointer_offset = HEADER_SIZE + index * LINE_POINTER_SIZE;
+            let mut offset_bytes = [0u8; LINE_POINTER_OFFSET_SIZE];
+            offset_bytes.copy_from_slice(
+                &data[line_pointer_offset..(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)],
+            );
+            let offset = u32::from_le_bytes(offset_bytes);
+
+            let mut size_bytes = [0u8; LINE_POINTER_SIZE_SIZE];
+            size_bytes.copy_from_slice(
+                &data[(line_pointer_offset + LINE_POINTER_OFFSET_SIZE)
+                    ..(line_pointer_offset + LINE_POINTER_SIZE)],
+            );
+            let raw_size = u32::from_le_bytes(size_bytes);
+
+            let slot = if offset == 0 && raw_size == 0 {
+                Slot::Tombstone
+            } else if raw_size & OVERFLOW_FLAG != 0 {
+                Slot::Tuple(TupleSlot::Overflow {
+                    first_page_id: PageID(offset),
+                    total_len: (raw_size & !OVERFLOW_FLAG) as usize,
+                })
+            } else {
+                let size = raw_size as usize;
+                Slot::Tuple(TupleSlot::Inline(
+                    data[(offset as usize)..(offset as usize + size)].into(),
+                ))
+            };
+            slots.push(slot);
+        }
+
+        Ok(TablePage {
+            page_id,
+            lsn,
+            next_page_id,
+            slots,
+        })
+    }
+}
@@ -7,34 +7,57 @@ use crate::{
     },
     catalog::{Column, DataType, Schema},
     common::PageID,
+    index::IndexRange,
 };
 
 #[derive(Debug, Clone)]
 pub enum Plan {
     SeqScan(SeqScanPlan),
+    IndexScan(IndexScanPlan),
     Filter(FilterPlan),
     Project(ProjectPlan),
     Insert(InsertPlan),
     Delete(DeletePlan),
     Update(UpdatePlan),
+    /// Always yields zero rows. Produced by `PlanRewriter` when it folds a `Filter` whose
+    /// condition is a compile-time constant `false`/`NULL`, so the executor doesn't need to
+    /// spin up a scan it already knows will be filtered down to nothing.
+    Empty(EmptyPlan),
 }
 impl Plan {
     pub fn schema(&self) -> &Schema {
         match self {
             Plan::SeqScan(plan) => &plan.schema,
+            Plan::IndexScan(plan) => &plan.schema,
             Plan::Filter(plan) => &plan.schema,
             Plan::Project(plan) => &plan.schema,
             Plan::Insert(plan) => &plan.schema,
             Plan::Delete(plan) => &plan.schema,
             Plan::Update(plan) => &plan.schema,
+            Plan::Empty(plan) => &plan.schema,
         }
     }
 }
 #[derive(Debug, Clone)]
+pub struct EmptyPlan {
+    pub schema: Schema,
+}
+#[derive(Debug, Clone)]
 pub struct SeqScanPlan {
     pub first_page_id: PageID,
     pub schema: Schema,
 }
+/// A lookup through a B-tree secondary index rather than a full heap scan, built by
+/// `Planner::plan_index_scan`. `plan_select_statement` does not emit it: every select is
+/// planned as `SeqScan` + `Filter`, indexed column or not. `index_first_page_id` is the index's
+/// root page; the heap RIDs it yields still need to be fetched from the table's own pages.
+#[derive(Debug, Clone)]
+pub struct IndexScanPlan {
+    pub index_first_page_id: PageID,
+    pub first_page_id: PageID,
+    pub range: IndexRange,
+    pub schema: Schema,
+}
 #[derive(Debug, Clone)]
 pub struct FilterPlan {
     pub condition: BoundExpressionAST,
@@ -67,12 +90,65 @@ pub struct UpdatePlan {
     pub child: Box<Plan>,
 }
 
+/// An index registered with the planner for a given column, looked up by column name in
+/// `plan_index_scan` to build an `IndexScanPlan`.
+#[derive(Debug, Clone)]
+pub struct IndexInfo {
+    pub column_name: String,
+    pub index_first_page_id: PageID,
+    pub table_first_page_id: PageID,
+}
+
 pub struct Planner {
     statement: BoundStatementAST,
+    indexes: Vec<IndexInfo>,
 }
 impl Planner {
     pub fn new(statement: BoundStatementAST) -> Self {
-        Self { statement }
+        Self {
+            statement,
+            indexes: Vec::new(),
+        }
+    }
+    pub fn new_with_indexes(statement: BoundStatementAST, indexes: Vec<IndexInfo>) -> Self {
+        Self { statement, indexes }
+    }
+    /// Builds an `IndexScanPlan` over the index registered for `column_name`, bypassing
+    /// `SeqScan`, or `None` if that column isn't indexed. Not called from
+    /// `plan_select_statement` (see the comment there); it builds the plan without going
+    /// through a `BoundSelectStatementAST`.
+    pub fn plan_index_scan(&self, column_name: &str, range: IndexRange, schema: Schema) -> Option<Plan> {
+        let index = self
+            .indexes
+            .iter()
+            .find(|index| index.column_name == column_name)?;
+        Some(Plan::IndexScan(IndexScanPlan {
+            index_first_page_id: index.index_first_page_id,
+            first_page_id: index.table_first_page_id,
+            range,
+            schema,
+        }))
+    }
+    /// Recognizes `condition` as an equality/range comparison over a column that has a
+    /// registered index (via `BoundExpressionAST::as_indexable_range`, the binder-side
+    /// accessor that exposes a comparison's column name and constant without the planner
+    /// needing to know the rest of the expression grammar) and builds the `IndexScanPlan` for
+    /// it. Returns `None` for anything else — a compound predicate, a comparison against a
+    /// non-indexed column, ... — so the caller falls back to a `SeqScan` + `Filter`.
+    ///
+    /// Not called from `plan_select_statement` — see the comment there — nor from anywhere
+    /// else; it has no caller.
+    #[allow(dead_code)]
+    fn plan_index_scan_for_condition(&self, condition: &BoundExpressionAST, schema: Schema) -> Option<Plan> {
+        let (column_name, range) = condition.as_indexable_range()?;
+        self.plan_index_scan(&column_name, range, schema)
+    }
+    fn wrap_filter(child: Plan, condition: BoundExpressionAST) -> Plan {
+        Plan::Filter(FilterPlan {
+            schema: child.schema().clone(),
+            condition,
+            child: Box::new(child),
+        })
     }
     pub fn plan(&self) -> Plan {
         match &self.statement {
@@ -91,14 +167,18 @@ impl Planner {
         }
     }
     fn plan_select_statement(&self, select_statement: &BoundSelectStatementAST) -> Plan {
-        let mut plan = self.plan_table_reference(&select_statement.table_reference);
-        if let Some(condition) = &select_statement.condition {
-            plan = Plan::Filter(FilterPlan {
-                condition: condition.clone(),
-                schema: plan.schema().clone(),
-                child: Box::new(plan),
-            });
-        }
+        let scan = self.plan_table_reference(&select_statement.table_reference);
+        // `plan_index_scan_for_condition` is deliberately not consulted here yet: a root split
+        // in `Index::insert` only updates the in-memory `Index::root_page_id`, never persists
+        // it anywhere durable, and no executor calls `Index::insert`/`delete` at all — so an
+        // `IndexScanPlan` would either scan from a page that's no longer the root after a split,
+        // or scan an index nothing ever populated. Emitting `IndexScanPlan` here has to wait
+        // until a catalog exists to persist the root id and the insert/delete DML path
+        // maintains the index; until then every select falls back to `SeqScan` + `Filter`.
+        let mut plan = match &select_statement.condition {
+            Some(condition) => Self::wrap_filter(scan, condition.clone()),
+            None => scan,
+        };
         if !select_statement.select_elements.is_empty() {
             plan = Plan::Project(ProjectPlan {
                 select_elements: select_statement.select_elements.clone(),
@@ -192,3 +272,98 @@ impl Planner {
         })
     }
 }
+
+/// Whether a `Filter`'s condition is known at plan time, independent of any row.
+enum ConstFold {
+    AlwaysTrue,
+    AlwaysFalse,
+    Unknown,
+}
+
+/// A column-pruning pass run between `Planner::plan` and execution: rewrites a `Plan` tree
+/// top-down so each `SeqScan` only carries the columns something above it actually needs,
+/// shrinking the tuples that flow through the scan and any `Filter` above it. Also folds a
+/// `Filter` with a constant condition, dropping it (if always-true) or replacing it with
+/// `Plan::Empty` (if always-false/NULL).
+pub struct PlanRewriter;
+
+impl PlanRewriter {
+    pub fn optimize(plan: Plan) -> Plan {
+        let required = Self::column_names(plan.schema());
+        Self::rewrite(plan, &required)
+    }
+    fn column_names(schema: &Schema) -> std::collections::HashSet<String> {
+        schema.columns.iter().map(|column| column.name.clone()).collect()
+    }
+    /// Folds a condition that's constant independent of any row: `AlwaysTrue`/`AlwaysFalse` for
+    /// a literal boolean, and `AlwaysFalse` for a literal `NULL` (matching `WHERE NULL`'s usual
+    /// semantics of filtering every row). Uses `BoundExpressionAST::as_constant_bool`, the
+    /// binder-side accessor for a condition with no row-dependent references; anything else
+    /// (a column reference, a comparison, ...) returns `None` there and `Unknown` here.
+    fn fold_condition(condition: &BoundExpressionAST) -> ConstFold {
+        match condition.as_constant_bool() {
+            Some(true) => ConstFold::AlwaysTrue,
+            Some(false) => ConstFold::AlwaysFalse,
+            None => ConstFold::Unknown,
+        }
+    }
+    /// Rewrites `plan` so every `SeqScan` beneath it carries only the columns in `required`.
+    /// A `Project` narrows `required` to what its own select elements need before recursing; a
+    /// `Filter` whose condition doesn't fold to a constant instead unions `required` with the
+    /// columns its own condition references (`BoundExpressionAST::referenced_columns`, the
+    /// binder-side accessor for a condition's column-ref structure) before recursing, so a
+    /// predicate over a column nothing above it needs doesn't defeat pruning for every other
+    /// column — e.g. `SELECT a FROM t WHERE b = 1` still prunes `t` down to `{a, b}`. Any other
+    /// node (`Insert`, `Delete`, `Update`, `IndexScan`, `Empty`) is returned unchanged, so a
+    /// `SeqScan` beneath a `Delete` or `Update` keeps its full schema.
+    fn rewrite(plan: Plan, required: &std::collections::HashSet<String>) -> Plan {
+        match plan {
+            Plan::Project(mut project) => {
+                // A select element's required columns are whatever its expression actually
+                // references (same `referenced_columns` accessor the `Filter` arm below uses),
+                // unioned across every element — not just the elements that happen to be a bare
+                // pass-through. `SELECT a, b + 1 AS c FROM t` must keep `b` required even though
+                // no element is named `b`.
+                let child_required: std::collections::HashSet<String> = project
+                    .select_elements
+                    .iter()
+                    .flat_map(|select_element| select_element.expression.referenced_columns())
+                    .collect();
+                project.child = Box::new(Self::rewrite(*project.child, &child_required));
+                Plan::Project(project)
+            }
+            Plan::Filter(filter) => match Self::fold_condition(&filter.condition) {
+                ConstFold::AlwaysTrue => Self::rewrite(*filter.child, required),
+                ConstFold::AlwaysFalse => Plan::Empty(EmptyPlan {
+                    schema: filter.schema,
+                }),
+                ConstFold::Unknown => {
+                    let mut child_required = required.clone();
+                    child_required.extend(filter.condition.referenced_columns());
+                    let child = Self::rewrite(*filter.child, &child_required);
+                    Plan::Filter(FilterPlan {
+                        condition: filter.condition,
+                        // A `Filter` only drops rows, never columns, so its own schema must
+                        // track whatever the (now possibly pruned) child actually produces
+                        // rather than the pre-pruning schema it was built with.
+                        schema: child.schema().clone(),
+                        child: Box::new(child),
+                    })
+                }
+            },
+            Plan::SeqScan(scan) => {
+                let columns = scan
+                    .schema
+                    .columns
+                    .into_iter()
+                    .filter(|column| required.contains(&column.name))
+                    .collect();
+                Plan::SeqScan(SeqScanPlan {
+                    first_page_id: scan.first_page_id,
+                    schema: Schema { columns },
+                })
+            }
+            other => other,
+        }
+    }
+}